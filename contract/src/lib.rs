@@ -7,37 +7,101 @@
 //! [decrement]: struct.Counter.html#method.decrement
 //! [get_num]: struct.Counter.html#method.get_num
 //! [reset]: struct.Counter.html#method.reset
-use std::collections::HashMap;
-
 use borsh::{BorshDeserialize, BorshSerialize};
-use near_sdk::{env, near_bindgen};
+use near_sdk::collections::{LookupMap, TreeMap, UnorderedSet};
+use near_sdk::{env, near_bindgen, AccountId, Balance, Promise};
 
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+/// Storage prefix for the per-account counter map, so the collection's keys
+/// don't collide with any other top-level collection we add to this contract.
+const COUNTERS_STORAGE_KEY: &[u8] = b"c";
+/// Storage prefix for the per-account cumulative donation map.
+const DONATIONS_STORAGE_KEY: &[u8] = b"d";
+/// Storage prefix for the value -> accounts leaderboard index. Each bucket
+/// (one per distinct counter value) gets its own `UnorderedSet`, prefixed
+/// with this plus the value's bytes so their keys can't collide.
+const LEADERBOARD_STORAGE_KEY: &[u8] = b"l";
+
+/// One NEAR, expressed in yoctoNEAR (10^24), the unit `attached_deposit` is in.
+const ONE_NEAR: Balance = 1_000_000_000_000_000_000_000_000;
+/// Share of a donation forwarded to `owner_id`; the remainder is retained to
+/// help cover this contract's storage staking.
+const OWNER_SHARE_NUMERATOR: Balance = 95;
+const OWNER_SHARE_DENOMINATOR: Balance = 100;
+
+/// Upper bound on how many leaderboard buckets `get_rank` will walk above the
+/// queried value before giving up. Without this, an account's rank on a
+/// leaderboard with many distinct counter values would cost one trie read
+/// per bucket above it, an unbounded view-call cost as the leaderboard grows.
+const MAX_RANK_BUCKETS_SCANNED: u32 = 100;
+
 // add the following attributes to prepare your code for serialization and invocation on the blockchain
 // More built-in Rust attributes here: https://doc.rust-lang.org/reference/attributes.html#built-in-attributes-index
 #[near_bindgen]
-#[derive(Default, BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize)]
 pub struct Counter {
     // See more data types at https://doc.rust-lang.org/book/ch03-02-data-types.html
-    user_counters: HashMap<String, i8>,
+    user_counters: LookupMap<AccountId, i64>,
+    /// Inclusive lower bound a counter may not be decremented past, if configured.
+    min: Option<i64>,
+    /// Inclusive upper bound a counter may not be incremented past, if configured.
+    max: Option<i64>,
+    /// Account that receives the forwarded share of every donation.
+    owner_id: AccountId,
+    /// Cumulative yoctoNEAR donated by each account, used to track the top supporter.
+    donations: LookupMap<AccountId, Balance>,
+    /// Account with the highest cumulative donation total, and that total.
+    top_supporter: (AccountId, Balance),
+    /// Whole NEAR required per counter step in `donate_and_increment`.
+    near_per_step: u64,
+    /// Index from counter value to the set of accounts currently holding it,
+    /// kept in sync incrementally so `get_leaderboard` never has to sort.
+    leaderboard: TreeMap<i64, UnorderedSet<AccountId>>,
+}
+
+// near_bindgen falls back to Default::default() if a view method is called
+// before the contract has been initialized; a LookupMap can't be conjured out
+// of thin air, so make that case panic instead of silently constructing a
+// broken contract.
+impl Default for Counter {
+    fn default() -> Self {
+        env::panic(b"Counter should be initialized before usage")
+    }
 }
 
 #[near_bindgen]
 impl Counter {
     /// Init attribute used for instantiation.
+    ///
+    /// `min` / `max` are optional saturation bounds: once set, `increment` and
+    /// `decrement` refuse to move a counter past them instead of wrapping.
+    /// `owner_id` receives the forwarded share of every `donate_and_increment` call.
+    /// `near_per_step` is how many whole NEAR `donate_and_increment` requires per
+    /// counter step; deployers can raise or lower this donation-to-step rate.
     #[init]
-    pub fn new() -> Self {
+    pub fn new(owner_id: AccountId, min: Option<i64>, max: Option<i64>, near_per_step: u64) -> Self {
         // useful snippet to copy/paste, making sure state isn't already initialized
         assert!(env::state_read::<Self>().is_none(), "Already initialized");
+        if let (Some(min), Some(max)) = (min, max) {
+            assert!(min <= max, "min must not be greater than max");
+        }
+        assert!(near_per_step > 0, "near_per_step must be positive");
         // notice we've chosen to use an implicit "return" here
         Self {
-            user_counters: HashMap::new(),
+            user_counters: LookupMap::new(COUNTERS_STORAGE_KEY.to_vec()),
+            min,
+            max,
+            owner_id: owner_id.clone(),
+            donations: LookupMap::new(DONATIONS_STORAGE_KEY.to_vec()),
+            top_supporter: (owner_id, 0),
+            leaderboard: TreeMap::new(LEADERBOARD_STORAGE_KEY.to_vec()),
+            near_per_step,
         }
     }
 
-    /// Returns 8-bit signed integer representing the number for the account argument.
+    /// Returns 64-bit signed integer representing the number for the account argument.
     ///
     /// Note, the parameter is &self (without being mutable) meaning it doesn't modify state.
     /// In the frontend (/src/main.js) this is added to the "viewMethods" array
@@ -46,7 +110,7 @@ impl Counter {
     /// ```bash
     /// near view counter.YOU.testnet get_num '{"account": "donation.YOU.testnet"}'
     /// ```
-    pub fn get_num(&self, account: String) -> i8 {
+    pub fn get_num(&self, account: String) -> i64 {
         // call our first private function
         // try removing the .clone() below and note the error. this may happen from time to time
         // (learn more about Rust ownership later: https://doc.rust-lang.org/nomicon/ownership.html)
@@ -60,9 +124,10 @@ impl Counter {
     }
 
     // our first private functions
-    fn get_num_from_signer(&self, account: String) -> i8 {
-        // notice we've chosen to use an implicit "return" here
-        self.user_counters.get(&account).cloned().unwrap_or(0)
+    fn get_num_from_signer(&self, account: String) -> i64 {
+        // only the touched key is loaded from trie storage, unlike a HashMap
+        // which would deserialize every account's counter on every call
+        self.user_counters.get(&account).unwrap_or(0)
     }
 
     /// Increment the counter *per account* that calls it.
@@ -75,14 +140,16 @@ impl Counter {
     /// near call counter.YOU.testnet increment --accountId donation.YOU.testnet
     /// ```
     pub fn increment(&mut self) {
-        // note: adding one like this is an easy way to accidentally overflow
-        // real smart contracts will want to have safety checks
         let caller = env::signer_account_id();
-        let current_val = self.user_counters.get(&caller).cloned().unwrap_or(0);
-        self.user_counters.insert(caller.clone(), current_val + 1);
-
-        // this will panic if it's not added (but we know it's there)
-        let counter_val = self.user_counters[&caller];
+        let current_val = self.user_counters.get(&caller).unwrap_or(0);
+        let counter_val = current_val
+            .checked_add(1)
+            .unwrap_or_else(|| env::panic(b"Counter overflowed i64"));
+        if let Some(max) = self.max {
+            assert!(counter_val <= max, "Counter would exceed configured max");
+        }
+        self.user_counters.insert(&caller, &counter_val);
+        self.leaderboard_move(&caller, current_val, counter_val);
 
         let log_message = format!("Incremented to {}", counter_val);
         env::log(log_message.as_bytes());
@@ -98,15 +165,18 @@ impl Counter {
     /// near call counter.YOU.testnet decrement --accountId donation.YOU.testnet
     /// ```
     pub fn decrement(&mut self) {
-        // note: subtracting one like this is an easy way to accidentally overflow
-        // real smart contracts will want to have safety checks
         let caller = env::signer_account_id();
-        // we'll use a slightly different approach to illustrate dereferencing (the "*")
-        // see https://doc.rust-lang.org/book/ch08-03-hash-maps.html#updating-a-value-based-on-the-old-value
-        let count = self.user_counters.entry(caller).or_insert(0);
-        *count -= 1;
+        let current_val = self.user_counters.get(&caller).unwrap_or(0);
+        let counter_val = current_val
+            .checked_sub(1)
+            .unwrap_or_else(|| env::panic(b"Counter overflowed i64"));
+        if let Some(min) = self.min {
+            assert!(counter_val >= min, "Counter would exceed configured min");
+        }
+        self.user_counters.insert(&caller, &counter_val);
+        self.leaderboard_move(&caller, current_val, counter_val);
 
-        let log_message = format!("Decreased number to {}", count);
+        let log_message = format!("Decreased number to {}", counter_val);
         env::log(log_message.as_bytes());
         after_counter_change();
     }
@@ -114,18 +184,140 @@ impl Counter {
     /// Reset to zero.
     pub fn reset(&mut self) {
         let caller = env::signer_account_id();
-        self.user_counters.insert(caller, 0);
+        let current_val = self.user_counters.get(&caller).unwrap_or(0);
+        self.user_counters.insert(&caller, &0);
+        self.leaderboard_move(&caller, current_val, 0);
         // Another way to log on NEAR is to cast a string into bytes, hence "b" below:
         env::log(b"Reset counter to zero");
     }
+
+    /// Increments the caller's counter by one step per `near_per_step` whole
+    /// NEAR attached, forwards `OWNER_SHARE_NUMERATOR / OWNER_SHARE_DENOMINATOR`
+    /// of the deposit to `owner_id`, and updates `top_supporter` if the
+    /// caller's cumulative donations now lead. Mirrors the "Buy Me A Coffee" pattern.
+    #[payable]
+    pub fn donate_and_increment(&mut self) -> Promise {
+        let caller = env::signer_account_id();
+        let deposit = env::attached_deposit();
+        let step_yocto = (self.near_per_step as Balance) * ONE_NEAR;
+        assert!(
+            deposit >= step_yocto,
+            "Attach at least {} NEAR to donate",
+            self.near_per_step
+        );
+
+        let steps = (deposit / step_yocto) as i64;
+        let current_val = self.user_counters.get(&caller).unwrap_or(0);
+        let counter_val = current_val
+            .checked_add(steps)
+            .unwrap_or_else(|| env::panic(b"Counter overflowed i64"));
+        if let Some(max) = self.max {
+            assert!(counter_val <= max, "Counter would exceed configured max");
+        }
+        self.user_counters.insert(&caller, &counter_val);
+        self.leaderboard_move(&caller, current_val, counter_val);
+
+        let total_donated = self.donations.get(&caller).unwrap_or(0) + deposit;
+        self.donations.insert(&caller, &total_donated);
+        if total_donated > self.top_supporter.1 {
+            self.top_supporter = (caller.clone(), total_donated);
+        }
+
+        let log_message = format!(
+            "{} donated {} yoctoNEAR, incremented to {}",
+            caller, deposit, counter_val
+        );
+        env::log(log_message.as_bytes());
+        after_counter_change();
+
+        let owner_amount = deposit * OWNER_SHARE_NUMERATOR / OWNER_SHARE_DENOMINATOR;
+        Promise::new(self.owner_id.clone()).transfer(owner_amount)
+    }
+
+    /// Returns the account with the highest cumulative donation total, and that total.
+    pub fn get_top_supporter(&self) -> (AccountId, Balance) {
+        self.top_supporter.clone()
+    }
+
+    /// Returns up to `limit` accounts sorted descending by counter value.
+    pub fn get_leaderboard(&self, limit: u32) -> Vec<(AccountId, i64)> {
+        let mut leaderboard = Vec::new();
+        'buckets: for (value, bucket) in self.leaderboard.iter_rev() {
+            for account in bucket.iter() {
+                if leaderboard.len() as u32 >= limit {
+                    break 'buckets;
+                }
+                leaderboard.push((account, value));
+            }
+        }
+        leaderboard
+    }
+
+    /// Returns the 1-based rank of `account` among all tracked counters,
+    /// highest value first. Accounts tied on value share the same rank.
+    ///
+    /// Walks at most `MAX_RANK_BUCKETS_SCANNED` buckets above the account's
+    /// value to keep this view call gas-bounded; if the account sits behind
+    /// more distinct values than that, the returned rank is a lower bound
+    /// rather than the exact rank.
+    pub fn get_rank(&self, account: String) -> u32 {
+        let value = self.user_counters.get(&account).unwrap_or(0);
+        let mut rank: u32 = 1;
+        let mut buckets_scanned: u32 = 0;
+        for (bucket_value, bucket) in self.leaderboard.iter_rev() {
+            if bucket_value <= value {
+                break;
+            }
+            if buckets_scanned >= MAX_RANK_BUCKETS_SCANNED {
+                return rank;
+            }
+            rank += bucket.len() as u32;
+            buckets_scanned += 1;
+        }
+        rank
+    }
+
+    /// Moves `account` from the `old_value` bucket to the `new_value` bucket
+    /// of the leaderboard index, removing the old bucket once it's empty.
+    fn leaderboard_move(&mut self, account: &AccountId, old_value: i64, new_value: i64) {
+        if old_value == new_value {
+            return;
+        }
+        if let Some(mut bucket) = self.leaderboard.get(&old_value) {
+            bucket.remove(account);
+            if bucket.is_empty() {
+                self.leaderboard.remove(&old_value);
+            } else {
+                self.leaderboard.insert(&old_value, &bucket);
+            }
+        }
+
+        let mut bucket = self
+            .leaderboard
+            .get(&new_value)
+            .unwrap_or_else(|| UnorderedSet::new(leaderboard_bucket_prefix(new_value)));
+        bucket.insert(account);
+        self.leaderboard.insert(&new_value, &bucket);
+    }
+}
+
+/// Builds the storage prefix for the `UnorderedSet` backing one leaderboard
+/// bucket, so each distinct counter value gets keys that can't collide with
+/// any other bucket's.
+fn leaderboard_bucket_prefix(value: i64) -> Vec<u8> {
+    let mut prefix = LEADERBOARD_STORAGE_KEY.to_vec();
+    prefix.extend_from_slice(&value.to_be_bytes());
+    prefix
 }
 
 // unlike the struct's functions above, this function cannot use attributes #[derive(…)] or #[near_bindgen]
 // any attempts will throw helpful warnings upon 'cargo build'
 // while this function cannot be invoked directly on the blockchain, it can be called from an invoked function
 fn after_counter_change() {
-    // show helpful warning that i8 (8-bit signed integer) will overflow above 127 or below -128
-    env::log(b"Make sure you don't overflow, my friend.");
+    // overflow (via checked_add/checked_sub) and the configured min/max
+    // bounds (via assert!) are enforced above before state is ever written,
+    // so there's nothing left here to warn about
+    env::log(b"Counter updated within configured bounds.");
 }
 
 /*
@@ -144,6 +336,15 @@ mod tests {
     // part of writing unit tests is setting up a mock context
     // this is also a useful list to peek at when wondering what's available in env::*
     fn get_context(input: Vec<u8>, is_view: bool, signer: String) -> VMContext {
+        get_context_with_deposit(input, is_view, signer, 0)
+    }
+
+    fn get_context_with_deposit(
+        input: Vec<u8>,
+        is_view: bool,
+        signer: String,
+        attached_deposit: Balance,
+    ) -> VMContext {
         VMContext {
             current_account_id: "alice.testnet".to_string(),
             signer_account_id: signer,
@@ -155,7 +356,7 @@ mod tests {
             account_balance: 0,
             account_locked_balance: 0,
             storage_usage: 0,
-            attached_deposit: 0,
+            attached_deposit,
             prepaid_gas: 10u64.pow(18),
             random_seed: vec![0, 1, 2],
             is_view,
@@ -172,7 +373,7 @@ mod tests {
         let context = get_context(vec![], false, "robert.testnet".to_string());
         testing_env!(context);
         // instantiate a contract variable with the counter at zero
-        let mut contract = Counter::new();
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
         contract.increment();
         // we can do println! in tests, but reminder to use env::log outside of tests
         println!("Value after increment: {}", contract.get_num("robert.testnet".to_string()));
@@ -184,7 +385,7 @@ mod tests {
     fn decrement() {
         let context = get_context(vec![], false, "robert.testnet".to_string());
         testing_env!(context);
-        let mut contract = Counter::new();
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
         contract.decrement();
         println!("Value after decrement: {}", contract.get_num("robert.testnet".to_string()));
         // confirm that we received -1 when calling get_num
@@ -195,7 +396,7 @@ mod tests {
     fn increment_and_reset() {
         let context = get_context(vec![], false, "robert.testnet".to_string());
         testing_env!(context);
-        let mut contract = Counter::new();
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
         contract.increment();
         contract.reset();
         println!("Value after reset: {}", contract.get_num("robert.testnet".to_string()));
@@ -209,7 +410,7 @@ mod tests {
         let context_alice = get_context(vec![], false, "alice.testnet".to_string());
         // increment twice on robert's account
         testing_env!(context_robert);
-        let mut contract = Counter::new();
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
         contract.increment();
         contract.increment();
         // decrement once on alice's account
@@ -221,4 +422,219 @@ mod tests {
         assert_eq!(2, contract.get_num("robert.testnet".to_string()));
         assert_eq!(-1, contract.get_num("alice.testnet".to_string()));
     }
+
+    #[test]
+    #[should_panic(expected = "Counter overflowed i64")]
+    fn increment_panics_on_i64_overflow() {
+        let context = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context);
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
+        // drive the counter right up to the boundary without looping i64::MAX times
+        contract.user_counters.insert(&"robert.testnet".to_string(), &i64::MAX);
+        contract.increment();
+    }
+
+    #[test]
+    #[should_panic(expected = "Counter overflowed i64")]
+    fn decrement_panics_on_i64_underflow() {
+        let context = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context);
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
+        contract.user_counters.insert(&"robert.testnet".to_string(), &i64::MIN);
+        contract.decrement();
+    }
+
+    #[test]
+    #[should_panic(expected = "Counter would exceed configured max")]
+    fn increment_rejects_crossing_configured_max() {
+        let context = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context);
+        let mut contract = Counter::new("owner.testnet".to_string(), None, Some(5), 1);
+        contract.user_counters.insert(&"robert.testnet".to_string(), &5);
+        contract.increment();
+    }
+
+    #[test]
+    #[should_panic(expected = "Counter would exceed configured min")]
+    fn decrement_rejects_crossing_configured_min() {
+        let context = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context);
+        let mut contract = Counter::new("owner.testnet".to_string(), Some(-5), None, 1);
+        contract.user_counters.insert(&"robert.testnet".to_string(), &-5);
+        contract.decrement();
+    }
+
+    #[test]
+    fn increment_and_decrement_stay_within_configured_bounds() {
+        let context = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context);
+        let mut contract = Counter::new("owner.testnet".to_string(), Some(-1), Some(1), 1);
+        contract.increment();
+        assert_eq!(1, contract.get_num("robert.testnet".to_string()));
+        contract.decrement();
+        contract.decrement();
+        assert_eq!(-1, contract.get_num("robert.testnet".to_string()));
+    }
+
+    #[test]
+    fn donate_and_increment_steps_by_near_per_step_and_tracks_top_supporter() {
+        let context = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context);
+        // near_per_step of 2 means one counter step per 2 whole NEAR attached
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 2);
+
+        let deposit_context = get_context_with_deposit(
+            vec![],
+            false,
+            "robert.testnet".to_string(),
+            5 * ONE_NEAR,
+        );
+        testing_env!(deposit_context);
+        contract.donate_and_increment();
+        // 5 NEAR at 2 NEAR per step rounds down to 2 steps
+        assert_eq!(2, contract.get_num("robert.testnet".to_string()));
+        assert_eq!(
+            ("robert.testnet".to_string(), 5 * ONE_NEAR),
+            contract.get_top_supporter()
+        );
+
+        let smaller_deposit_context = get_context_with_deposit(
+            vec![],
+            false,
+            "alice.testnet".to_string(),
+            2 * ONE_NEAR,
+        );
+        testing_env!(smaller_deposit_context);
+        contract.donate_and_increment();
+        // alice's cumulative donation is smaller than robert's, so the top
+        // supporter doesn't change
+        assert_eq!(1, contract.get_num("alice.testnet".to_string()));
+        assert_eq!(
+            ("robert.testnet".to_string(), 5 * ONE_NEAR),
+            contract.get_top_supporter()
+        );
+    }
+
+    #[test]
+    fn leaderboard_orders_accounts_descending_by_value() {
+        let context_robert = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context_robert);
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
+        contract.increment();
+        contract.increment();
+        contract.increment(); // robert = 3
+
+        let context_alice = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context_alice);
+        contract.increment(); // alice = 1
+
+        let context_carol = get_context(vec![], false, "carol.testnet".to_string());
+        testing_env!(context_carol);
+        contract.increment();
+        contract.increment(); // carol = 2
+
+        assert_eq!(
+            vec![
+                ("robert.testnet".to_string(), 3),
+                ("carol.testnet".to_string(), 2),
+                ("alice.testnet".to_string(), 1),
+            ],
+            contract.get_leaderboard(10)
+        );
+    }
+
+    #[test]
+    fn get_leaderboard_respects_limit_including_zero() {
+        let context = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context);
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
+        contract.increment();
+        contract.increment();
+
+        assert_eq!(Vec::<(AccountId, i64)>::new(), contract.get_leaderboard(0));
+        assert_eq!(
+            vec![("robert.testnet".to_string(), 2)],
+            contract.get_leaderboard(1)
+        );
+    }
+
+    #[test]
+    fn leaderboard_keeps_tied_accounts_in_the_same_bucket() {
+        let context_robert = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context_robert);
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
+        contract.increment();
+
+        let context_alice = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context_alice);
+        contract.increment();
+
+        let mut leaderboard = contract.get_leaderboard(10);
+        leaderboard.sort(); // tied accounts can come back from the bucket in either order
+        assert_eq!(
+            vec![
+                ("alice.testnet".to_string(), 1),
+                ("robert.testnet".to_string(), 1),
+            ],
+            leaderboard
+        );
+        assert_eq!(1, contract.get_rank("robert.testnet".to_string()));
+        assert_eq!(1, contract.get_rank("alice.testnet".to_string()));
+    }
+
+    #[test]
+    fn get_rank_accounts_for_accounts_strictly_above() {
+        let context_robert = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context_robert);
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
+        contract.increment();
+        contract.increment();
+        contract.increment(); // robert = 3
+
+        let context_alice = get_context(vec![], false, "alice.testnet".to_string());
+        testing_env!(context_alice);
+        contract.increment(); // alice = 1
+
+        assert_eq!(1, contract.get_rank("robert.testnet".to_string()));
+        assert_eq!(2, contract.get_rank("alice.testnet".to_string()));
+        // an account that never interacted defaults to 0 and ranks behind both
+        assert_eq!(3, contract.get_rank("dave.testnet".to_string()));
+    }
+
+    #[test]
+    fn leaderboard_removes_empty_buckets_when_an_account_moves_away() {
+        let context = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context);
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
+        contract.increment(); // robert = 1, bucket 1 now holds one account
+        contract.increment(); // robert = 2, bucket 1 should now be removed entirely
+
+        assert_eq!(
+            vec![("robert.testnet".to_string(), 2)],
+            contract.get_leaderboard(10)
+        );
+        assert!(contract.leaderboard.get(&1).is_none());
+    }
+
+    #[test]
+    fn get_rank_caps_scanned_buckets_to_bound_gas() {
+        let context = get_context(vec![], false, "robert.testnet".to_string());
+        testing_env!(context);
+        let mut contract = Counter::new("owner.testnet".to_string(), None, None, 1);
+
+        // robert never moves, so his implicit value stays 0; every account
+        // below gets a distinct value strictly above that
+        let distinct_values_above = MAX_RANK_BUCKETS_SCANNED + 3;
+        for i in 1..=distinct_values_above {
+            let account = format!("acct{}.testnet", i);
+            contract.leaderboard_move(&account, 0, i as i64);
+        }
+
+        // the scan should stop after MAX_RANK_BUCKETS_SCANNED buckets rather
+        // than walking all of them
+        assert_eq!(
+            1 + MAX_RANK_BUCKETS_SCANNED,
+            contract.get_rank("robert.testnet".to_string())
+        );
+    }
 }
@@ -1,11 +1,14 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use near_sdk::{AccountId, env, ext_contract, near_bindgen};
+use near_sdk::{env, ext_contract, near_bindgen, AccountId, Promise, PromiseResult};
 
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
-// For this example contract here's a hardcoded, prepaid gas value good for making a single, simple call
-const SINGLE_CALL_GAS: u64 = 200000000000000;
+// Explicit gas budgets for the remote increment call and the callback that
+// reports its outcome, split out of what used to be one hardcoded
+// SINGLE_CALL_GAS so a slow remote call can't starve the callback of gas.
+const INCREMENT_GAS: u64 = 100_000_000_000_000;
+const ON_INCREMENT_COMPLETE_GAS: u64 = 20_000_000_000_000;
 
 // If the name is not provided, the namespace for generated methods in derived by applying snake
 // case to the trait name, e.g. ext_my_counter
@@ -14,21 +17,129 @@ pub trait ExtMyCounter {
     fn increment(&mut self);
 }
 
+// Callback Donation calls on itself once the cross-contract increment
+// settles, so it can inspect the remote promise's result.
+#[ext_contract(ext_self)]
+pub trait ExtDonationSelf {
+    fn on_increment_complete(&mut self) -> bool;
+}
+
 // Add the following attributes to prepare your code for serialization and invocation on the blockchain.
 // More built-in Rust attributes here: https://doc.rust-lang.org/reference/attributes.html#built-in-attributes-index
-// Here an empty struct is okay, as we're only using this for a cross-contract call.
 #[near_bindgen]
 #[derive(Default, BorshDeserialize, BorshSerialize)]
-pub struct Donation {}
+pub struct Donation {
+    /// Whether the most recently chained `increment` call succeeded.
+    last_call_succeeded: bool,
+    /// Running count of remote `increment` calls that reverted or timed out.
+    failed_call_count: u64,
+}
 
 #[near_bindgen]
 impl Donation {
     // No #[init] attribute or new() function is needed here.
 
     /// The account_id is the NEAR account where the counter smart contract has been deployed
-    pub fn increment_my_number(&mut self, account_id: AccountId) {
+    pub fn increment_my_number(&mut self, account_id: AccountId) -> Promise {
         // The 0 is the amount of NEAR (Ⓝ) to send.
         // The final parameter is the amount of (extra) gas to add.
-        ext::increment(&account_id, 0, SINGLE_CALL_GAS);
+        ext::increment(&account_id, 0, INCREMENT_GAS).then(ext_self::on_increment_complete(
+            &env::current_account_id(),
+            0,
+            ON_INCREMENT_COMPLETE_GAS,
+        ))
     }
-}
\ No newline at end of file
+
+    /// Chained after `increment_my_number`'s cross-contract call; inspects
+    /// the remote promise's result and records whether it succeeded so
+    /// callers (and integration tests) can learn the outcome.
+    #[private]
+    pub fn on_increment_complete(&mut self) -> bool {
+        assert_eq!(env::promise_results_count(), 1, "Expected exactly one promise result");
+        let succeeded = matches!(env::promise_result(0), PromiseResult::Successful(_));
+        self.last_call_succeeded = succeeded;
+        if succeeded {
+            env::log(b"Remote increment call succeeded");
+        } else {
+            self.failed_call_count += 1;
+            env::log(b"Remote increment call failed");
+        }
+        succeeded
+    }
+
+    /// Whether the most recently chained `increment` call succeeded.
+    pub fn get_last_call_succeeded(&self) -> bool {
+        self.last_call_succeeded
+    }
+
+    /// Running count of remote `increment` calls that reverted or timed out.
+    pub fn get_failed_call_count(&self) -> u64 {
+        self.failed_call_count
+    }
+}
+
+/*
+ * the rest of this file sets up unit tests
+ * to run these, the command will be:
+ * cargo test -- --nocapture
+ */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::MockedBlockchain;
+    use near_sdk::{testing_env, VMContext};
+
+    fn get_context() -> VMContext {
+        VMContext {
+            current_account_id: "donation.testnet".to_string(),
+            signer_account_id: "robert.testnet".to_string(),
+            signer_account_pk: vec![0, 1, 2],
+            predecessor_account_id: "counter.testnet".to_string(),
+            input: vec![],
+            block_index: 0,
+            block_timestamp: 0,
+            account_balance: 0,
+            account_locked_balance: 0,
+            storage_usage: 0,
+            attached_deposit: 0,
+            prepaid_gas: 10u64.pow(18),
+            random_seed: vec![0, 1, 2],
+            is_view: false,
+            output_data_receivers: vec![],
+            epoch_height: 19,
+        }
+    }
+
+    #[test]
+    fn on_increment_complete_records_success() {
+        testing_env!(
+            get_context(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Successful(vec![])]
+        );
+        let mut contract = Donation::default();
+
+        assert!(contract.on_increment_complete());
+        assert!(contract.get_last_call_succeeded());
+        assert_eq!(0, contract.get_failed_call_count());
+    }
+
+    #[test]
+    fn on_increment_complete_records_failure() {
+        testing_env!(
+            get_context(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+        let mut contract = Donation::default();
+
+        assert!(!contract.on_increment_complete());
+        assert!(!contract.get_last_call_succeeded());
+        assert_eq!(1, contract.get_failed_call_count());
+    }
+}